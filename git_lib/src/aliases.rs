@@ -0,0 +1,92 @@
+use std::{
+    collections::HashMap,
+    sync::{Mutex, OnceLock},
+};
+
+fn alias_table() -> &'static Mutex<HashMap<String, String>> {
+    static TABLE: OnceLock<Mutex<HashMap<String, String>>> = OnceLock::new();
+    TABLE.get_or_init(|| {
+        let mut table = HashMap::new();
+        table.insert("gh".to_string(), "https://github.com/".to_string());
+        table.insert("gl".to_string(), "https://gitlab.com/".to_string());
+        Mutex::new(table)
+    })
+}
+
+/// Registers a custom shorthand prefix (e.g. `work:` for an internal Gitea), consulted by
+/// [`resolve_remote`] alongside the built-in `gh:`/`gl:` aliases.
+pub fn register_alias(prefix: &str, base_url: &str) {
+    alias_table()
+        .lock()
+        .expect("alias table lock poisoned")
+        .insert(prefix.to_string(), base_url.to_string());
+}
+
+/// Expands a shorthand remote like `gh:owner/repo` into its full clone url. Returns `remote`
+/// unchanged if its prefix isn't a registered alias.
+pub fn resolve_remote(remote: &str) -> String {
+    let Some((prefix, rest)) = remote.split_once(':') else {
+        return remote.to_string();
+    };
+
+    let table = alias_table().lock().expect("alias table lock poisoned");
+    let Some(base) = table.get(prefix) else {
+        return remote.to_string();
+    };
+
+    if rest.ends_with(".git") {
+        format!("{base}{rest}")
+    } else {
+        format!("{base}{rest}.git")
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn resolve_remote_expands_the_builtin_gh_alias() {
+        assert_eq!(
+            resolve_remote("gh:pitoniak32/git_repo"),
+            "https://github.com/pitoniak32/git_repo.git"
+        );
+    }
+
+    #[test]
+    fn resolve_remote_expands_the_builtin_gl_alias() {
+        assert_eq!(
+            resolve_remote("gl:pitoniak32/git_repo"),
+            "https://gitlab.com/pitoniak32/git_repo.git"
+        );
+    }
+
+    #[test]
+    fn resolve_remote_does_not_double_append_the_git_suffix() {
+        assert_eq!(
+            resolve_remote("gh:pitoniak32/git_repo.git"),
+            "https://github.com/pitoniak32/git_repo.git"
+        );
+    }
+
+    #[test]
+    fn resolve_remote_leaves_urls_with_an_unregistered_prefix_unchanged() {
+        let remote = "https://github.com/pitoniak32/git_repo.git";
+        assert_eq!(resolve_remote(remote), remote);
+    }
+
+    #[test]
+    fn resolve_remote_leaves_urls_without_a_prefix_unchanged() {
+        let remote = "pitoniak32/git_repo";
+        assert_eq!(resolve_remote(remote), remote);
+    }
+
+    #[test]
+    fn resolve_remote_expands_a_registered_custom_alias() {
+        register_alias("girepotest", "https://git.example.com/");
+        assert_eq!(
+            resolve_remote("girepotest:team/project"),
+            "https://git.example.com/team/project.git"
+        );
+    }
+}
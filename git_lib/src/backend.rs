@@ -0,0 +1,193 @@
+use std::{
+    cell::RefCell,
+    os::unix::process::ExitStatusExt,
+    path::{Path, PathBuf},
+    process::Output,
+};
+
+use crate::git::{CloneOptions, Git, GitCmdError};
+use crate::git_uri::GitUri;
+
+/// The subset of git plumbing `GitRepo` actually drives, abstracted so tests can swap in
+/// [`MockGit`] instead of shelling out to a real `git` binary. Apart from `parse_uri`, which
+/// `GitRepo` still calls on [`Git`] directly, scoped to what `GitRepo` calls through its
+/// backend — `Git::init`/`Git::add_remote` are lower-level helpers callers use
+/// directly and aren't part of any `GitRepo` workflow, so they stay out of this trait.
+pub trait GitBackend {
+    fn clone(&self, uri: &str, to_path: &Path, options: &CloneOptions) -> Result<Output, GitCmdError>;
+    fn get_remote_url(&self, remote_name: &str, repo_path: &Path) -> Result<Option<String>, GitCmdError>;
+    fn status(&self, repo_path: &Path) -> Result<Option<String>, GitCmdError>;
+    fn log(&self, repo_path: &Path) -> Result<Option<String>, GitCmdError>;
+    fn is_inside_worktree(&self, repo_path: &Path) -> bool;
+    fn parse_uri(&self, url: &str) -> Result<GitUri, GitCmdError>;
+}
+
+/// Backs [`GitBackend`] with the real `git` binary via [`Git`].
+#[derive(Debug, Clone, Copy, Default)]
+pub struct RealGit;
+
+impl GitBackend for RealGit {
+    fn clone(&self, uri: &str, to_path: &Path, options: &CloneOptions) -> Result<Output, GitCmdError> {
+        Git::clone_with(uri, to_path, options)
+    }
+
+    fn get_remote_url(&self, remote_name: &str, repo_path: &Path) -> Result<Option<String>, GitCmdError> {
+        Git::get_remote_url(remote_name, &repo_path)
+    }
+
+    fn status(&self, repo_path: &Path) -> Result<Option<String>, GitCmdError> {
+        Git::status(&repo_path)
+    }
+
+    fn log(&self, repo_path: &Path) -> Result<Option<String>, GitCmdError> {
+        Git::log(&repo_path)
+    }
+
+    fn is_inside_worktree(&self, repo_path: &Path) -> bool {
+        Git::is_inside_worktree(&repo_path)
+    }
+
+    fn parse_uri(&self, url: &str) -> Result<GitUri, GitCmdError> {
+        Git::parse_uri(url)
+    }
+}
+
+/// One recorded call made against a [`MockGit`], for tests to assert against.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum Invocation {
+    Clone { uri: String, to_path: PathBuf, options: CloneOptions },
+    GetRemoteUrl { remote_name: String, repo_path: PathBuf },
+    Status { repo_path: PathBuf },
+    Log { repo_path: PathBuf },
+    IsInsideWorktree { repo_path: PathBuf },
+    ParseUri { url: String },
+}
+
+/// A [`GitBackend`] that records every call made to it and returns canned responses, so
+/// `GitRepo` logic can be exercised without a real git install or network access.
+#[derive(Debug, Default)]
+pub struct MockGit {
+    pub invocations: RefCell<Vec<Invocation>>,
+    pub remote_url: Option<String>,
+    pub is_worktree: bool,
+    pub status: Option<String>,
+    pub log: Option<String>,
+    /// Overrides what `parse_uri` returns. Falls back to the real parser (a pure, offline
+    /// computation) when unset, since scripting it is rarely needed.
+    pub parsed_uri: Option<GitUri>,
+}
+
+impl MockGit {
+    fn record(&self, invocation: Invocation) {
+        self.invocations.borrow_mut().push(invocation);
+    }
+
+    fn scripted_output() -> Output {
+        std::process::Command::new("true")
+            .output()
+            .unwrap_or_else(|_| Output {
+                status: std::process::ExitStatus::from_raw(0),
+                stdout: vec![],
+                stderr: vec![],
+            })
+    }
+}
+
+impl GitBackend for MockGit {
+    fn clone(&self, uri: &str, to_path: &Path, options: &CloneOptions) -> Result<Output, GitCmdError> {
+        self.record(Invocation::Clone {
+            uri: uri.to_string(),
+            to_path: to_path.to_path_buf(),
+            options: options.clone(),
+        });
+        Ok(Self::scripted_output())
+    }
+
+    fn get_remote_url(&self, remote_name: &str, repo_path: &Path) -> Result<Option<String>, GitCmdError> {
+        self.record(Invocation::GetRemoteUrl {
+            remote_name: remote_name.to_string(),
+            repo_path: repo_path.to_path_buf(),
+        });
+        Ok(self.remote_url.clone())
+    }
+
+    fn status(&self, repo_path: &Path) -> Result<Option<String>, GitCmdError> {
+        self.record(Invocation::Status { repo_path: repo_path.to_path_buf() });
+        Ok(self.status.clone())
+    }
+
+    fn log(&self, repo_path: &Path) -> Result<Option<String>, GitCmdError> {
+        self.record(Invocation::Log { repo_path: repo_path.to_path_buf() });
+        Ok(self.log.clone())
+    }
+
+    fn is_inside_worktree(&self, repo_path: &Path) -> bool {
+        self.record(Invocation::IsInsideWorktree { repo_path: repo_path.to_path_buf() });
+        self.is_worktree
+    }
+
+    fn parse_uri(&self, url: &str) -> Result<GitUri, GitCmdError> {
+        self.record(Invocation::ParseUri { url: url.to_string() });
+        match &self.parsed_uri {
+            Some(uri) => Ok(uri.clone()),
+            None => Git::parse_uri(url),
+        }
+    }
+}
+
+/// Either the real `git` binary or a [`MockGit`] standing in for it.
+#[derive(Debug)]
+pub enum Backend {
+    Real(RealGit),
+    Mock(MockGit),
+}
+
+impl Default for Backend {
+    fn default() -> Self {
+        Backend::Real(RealGit)
+    }
+}
+
+impl GitBackend for Backend {
+    fn clone(&self, uri: &str, to_path: &Path, options: &CloneOptions) -> Result<Output, GitCmdError> {
+        match self {
+            Backend::Real(b) => b.clone(uri, to_path, options),
+            Backend::Mock(b) => b.clone(uri, to_path, options),
+        }
+    }
+
+    fn get_remote_url(&self, remote_name: &str, repo_path: &Path) -> Result<Option<String>, GitCmdError> {
+        match self {
+            Backend::Real(b) => b.get_remote_url(remote_name, repo_path),
+            Backend::Mock(b) => b.get_remote_url(remote_name, repo_path),
+        }
+    }
+
+    fn status(&self, repo_path: &Path) -> Result<Option<String>, GitCmdError> {
+        match self {
+            Backend::Real(b) => b.status(repo_path),
+            Backend::Mock(b) => b.status(repo_path),
+        }
+    }
+
+    fn log(&self, repo_path: &Path) -> Result<Option<String>, GitCmdError> {
+        match self {
+            Backend::Real(b) => b.log(repo_path),
+            Backend::Mock(b) => b.log(repo_path),
+        }
+    }
+
+    fn is_inside_worktree(&self, repo_path: &Path) -> bool {
+        match self {
+            Backend::Real(b) => b.is_inside_worktree(repo_path),
+            Backend::Mock(b) => b.is_inside_worktree(repo_path),
+        }
+    }
+
+    fn parse_uri(&self, url: &str) -> Result<GitUri, GitCmdError> {
+        match self {
+            Backend::Real(b) => b.parse_uri(url),
+            Backend::Mock(b) => b.parse_uri(url),
+        }
+    }
+}
@@ -1,13 +1,16 @@
 use git_url_parse::GitUrl;
+use secrecy::{ExposeSecret, SecretString};
 use std::{
     ffi::OsStr,
     io,
-    path::Path,
+    num::NonZeroU32,
+    path::{Path, PathBuf},
     process::{Command, Output, Stdio},
     str::{FromStr, ParseBoolError},
     string::FromUtf8Error,
 };
 use thiserror::Error;
+use tokio::process::Command as AsyncCommand;
 
 use crate::git_uri::GitUri;
 
@@ -33,10 +36,73 @@ pub enum GitCmdError {
 
     #[error("failed parsing git url: {0}")]
     ParseUriError(#[source] <GitUrl as FromStr>::Err),
+
+    #[error("failed to fetch: {0}")]
+    Fetch(#[source] io::Error),
+
+    #[error("failed to pull: {0}")]
+    Pull(#[source] io::Error),
+
+    #[error("destination {0} already exists and is not a git worktree")]
+    DestExists(String),
+
+    #[error("destination {0} is a git worktree with no `origin` remote configured")]
+    NoOriginRemote(String),
+
+    #[error("destination {path} is tracking {found}, not the requested {expected}")]
+    RemoteMismatch {
+        path: String,
+        expected: String,
+        found: String,
+    },
+
+    #[error("failed to set remote url: {0}")]
+    SetRemoteUrl(#[source] io::Error),
+
+    #[error("failed to build an authenticated clone url for {0}")]
+    InvalidCredentialUrl(String),
 }
 
 const GIT_COMMAND: &str = "git";
 
+/// A git commit sha, newtyped so callers can't confuse it with any other bare `String`.
+#[derive(Debug, Clone, PartialEq, Eq, PartialOrd, Ord, Hash)]
+pub struct Sha(pub String);
+
+impl std::fmt::Display for Sha {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        write!(f, "{}", self.0)
+    }
+}
+
+/// One entry of [`Git::log_structured`], parsed out of git's NUL/unit-separator log format so
+/// commit subjects containing newlines can't be mistaken for record boundaries.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct CommitInfo {
+    pub sha: Sha,
+    pub author_name: String,
+    pub author_email: String,
+    pub authored_at: String,
+    pub subject: String,
+}
+
+/// A credential to clone a private repo with: either HTTPS with a username + secret token, or
+/// SSH with an explicit private key (and optional passphrase).
+#[derive(Debug, Clone)]
+pub enum Credentials {
+    Https { user: String, token: SecretString },
+    Ssh { key_path: PathBuf, passphrase: Option<SecretString> },
+}
+
+/// Options threaded down into `git clone` by [`Git::clone_with`].
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub struct CloneOptions {
+    pub branch: Option<String>,
+    pub depth: Option<NonZeroU32>,
+    pub single_branch: bool,
+    pub recurse_submodules: bool,
+}
+
 pub struct Git;
 
 impl Git {
@@ -52,6 +118,32 @@ impl Git {
         .map_err(GitCmdError::Clone)
     }
 
+    /// Same as [`Git::clone`], but threading `options` down into the clone invocation (e.g. to
+    /// check out a specific branch or do a fast shallow clone for CI).
+    pub fn clone_with(uri: &str, to_path: &Path, options: &CloneOptions) -> Result<Output, GitCmdError> {
+        let mut args = vec!["clone".to_string()];
+
+        if let Some(branch) = &options.branch {
+            args.push("--branch".to_string());
+            args.push(branch.clone());
+        }
+        if let Some(depth) = options.depth {
+            args.push("--depth".to_string());
+            args.push(depth.to_string());
+        }
+        if options.single_branch {
+            args.push("--single-branch".to_string());
+        }
+        if options.recurse_submodules {
+            args.push("--recurse-submodules".to_string());
+        }
+
+        args.push(uri.to_string());
+        args.push(to_path.to_string_lossy().to_string());
+
+        wrap_cmd(GIT_COMMAND, args).map_err(GitCmdError::Clone)
+    }
+
     pub fn status<P>(repo_path: &P) -> Result<Option<String>, GitCmdError>
     where
         P: AsRef<Path>,
@@ -88,6 +180,54 @@ impl Git {
         Ok(Some(log))
     }
 
+    /// Structured twin of [`Git::log`]: parses `git log` into [`CommitInfo`]s instead of
+    /// forcing callers to re-parse git's human-readable output.
+    pub fn log_structured<P>(repo_path: &P, limit: Option<usize>) -> Result<Vec<CommitInfo>, GitCmdError>
+    where
+        P: AsRef<Path>,
+    {
+        let mut args = vec![
+            "log".to_string(),
+            "--pretty=format:%H%x1f%an%x1f%ae%x1f%aI%x1f%s".to_string(),
+            "-z".to_string(),
+        ];
+        if let Some(limit) = limit {
+            args.push(format!("-n{limit}"));
+        }
+
+        let output = wrap_cmd_dir(GIT_COMMAND, args, repo_path).map_err(GitCmdError::IsRepositoryIo)?;
+        let raw = String::from_utf8(output.stdout).map_err(GitCmdError::GetRemoteError)?;
+
+        Ok(raw
+            .split('\0')
+            .filter(|record| !record.trim().is_empty())
+            .filter_map(|record| {
+                let mut fields = record.splitn(5, '\u{1f}');
+                Some(CommitInfo {
+                    sha: Sha(fields.next()?.to_string()),
+                    author_name: fields.next()?.to_string(),
+                    author_email: fields.next()?.to_string(),
+                    authored_at: fields.next()?.to_string(),
+                    subject: fields.next()?.to_string(),
+                })
+            })
+            .collect())
+    }
+
+    /// Returns `HEAD`'s commit sha.
+    pub fn current_commit<P>(repo_path: &P) -> Result<Sha, GitCmdError>
+    where
+        P: AsRef<Path>,
+    {
+        let output = wrap_cmd_dir(GIT_COMMAND, ["rev-parse", "HEAD"], repo_path)
+            .map_err(GitCmdError::IsRepositoryIo)?;
+
+        Ok(Sha(String::from_utf8(output.stdout)
+            .map_err(GitCmdError::GetRemoteError)?
+            .trim()
+            .to_string()))
+    }
+
     pub fn init(path: &Path) -> Result<(), GitCmdError> {
         let _ = wrap_cmd_dir("git", ["init"], path).map_err(GitCmdError::InitError)?;
         Ok(())
@@ -124,6 +264,68 @@ impl Git {
         Ok(Some(remote))
     }
 
+    /// Clones a private repo using `credentials`: for HTTPS the remote is parsed and rewritten
+    /// with the credential embedded as basic auth (never logged, thanks to [`SecretString`]);
+    /// for SSH, `GIT_SSH_COMMAND` is pointed at the given private key for the duration of the
+    /// clone.
+    pub fn clone_auth(remote_url: &str, to_path: &Path, credentials: &Credentials) -> Result<Output, GitCmdError> {
+        match credentials {
+            Credentials::Https { user, token } => {
+                let parsed = Git::parse_uri(remote_url)?;
+                // Built directly from the `SecretString` rather than stashed into
+                // `parsed.user`/`parsed.token` first - those are plain `String` fields on a
+                // `Debug`/`Serialize` struct, so writing the secret there even briefly would
+                // defeat the point of holding it as a `SecretString` in the first place.
+                let authed_url = parsed
+                    .to_authenticated_url_with(user, token)
+                    .ok_or_else(|| GitCmdError::InvalidCredentialUrl(remote_url.to_string()))?;
+                Git::clone(&authed_url, to_path)
+            }
+            Credentials::Ssh { key_path, passphrase } => {
+                let ssh_command = format!("ssh -i {} -o IdentitiesOnly=yes", key_path.to_string_lossy());
+                let args = [
+                    "clone".to_string(),
+                    remote_url.to_string(),
+                    to_path.to_string_lossy().to_string(),
+                ];
+
+                match passphrase {
+                    Some(passphrase) => {
+                        let askpass_path = write_askpass_script(passphrase).map_err(GitCmdError::Clone)?;
+                        let result = wrap_cmd_with_ssh_askpass(GIT_COMMAND, args, &ssh_command, &askpass_path)
+                            .map_err(GitCmdError::Clone);
+                        let _ = std::fs::remove_file(&askpass_path);
+                        result
+                    }
+                    None => wrap_cmd_with_env(GIT_COMMAND, args, "GIT_SSH_COMMAND", &ssh_command)
+                        .map_err(GitCmdError::Clone),
+                }
+            }
+        }
+    }
+
+    /// Rewrites `remote_name`'s url in place, e.g. to rotate a forge API token embedded in an
+    /// already-cloned repo's `origin` without recloning.
+    pub fn set_remote_url<P>(remote_name: &str, new_url: &str, repo_path: &P) -> Result<(), GitCmdError>
+    where
+        P: AsRef<Path>,
+    {
+        let _ = wrap_cmd_dir(GIT_COMMAND, ["remote", "set-url", remote_name, new_url], repo_path)
+            .map_err(GitCmdError::SetRemoteUrl)?;
+        Ok(())
+    }
+
+    /// Whether `repo_path` has local modifications, via `git status --porcelain`.
+    pub fn is_dirty<P>(repo_path: &P) -> Result<bool, GitCmdError>
+    where
+        P: AsRef<Path>,
+    {
+        let output = wrap_cmd_dir(GIT_COMMAND, ["status", "--porcelain"], repo_path)
+            .map_err(GitCmdError::IsRepositoryIo)?;
+        let status = String::from_utf8(output.stdout).map_err(GitCmdError::GetRemoteError)?;
+        Ok(!status.trim().is_empty())
+    }
+
     pub fn is_inside_worktree<P>(repo_path: &P) -> bool
     where
         P: AsRef<Path>,
@@ -141,6 +343,47 @@ impl Git {
     pub fn parse_uri(url: &str) -> Result<GitUri, GitCmdError> {
         Ok(GitUri::from(GitUrl::parse(url).map_err(GitCmdError::ParseUriError)?))
     }
+
+    /// Idempotently brings `to_path` into line with `uri`: clones it if `to_path` doesn't exist
+    /// yet, or updates it in place via `fetch`/`pull` if it's already a worktree tracking `uri`.
+    pub fn ensure(uri: &str, to_path: &Path) -> Result<Output, GitCmdError> {
+        if !to_path.exists() {
+            return Git::clone(uri, to_path);
+        }
+
+        if !Git::is_inside_worktree(&to_path) {
+            return Err(GitCmdError::DestExists(to_path.to_string_lossy().to_string()));
+        }
+
+        let Some(origin) = Git::get_remote_url("origin", &to_path)? else {
+            return Err(GitCmdError::NoOriginRemote(to_path.to_string_lossy().to_string()));
+        };
+
+        if origin != uri {
+            return Err(GitCmdError::RemoteMismatch {
+                path: to_path.to_string_lossy().to_string(),
+                expected: uri.to_string(),
+                found: origin,
+            });
+        }
+
+        wrap_cmd_dir(GIT_COMMAND, ["fetch", "origin"], to_path).map_err(GitCmdError::Fetch)?;
+        wrap_cmd_dir(GIT_COMMAND, ["pull"], to_path).map_err(GitCmdError::Pull)
+    }
+
+    /// Async twin of [`Git::clone`], for driving many clones concurrently.
+    pub async fn clone_async(uri: &str, to_path: &Path) -> Result<Output, GitCmdError> {
+        wrap_cmd_async(
+            GIT_COMMAND,
+            [
+                "clone".to_string(),
+                uri.to_string(),
+                to_path.to_string_lossy().to_string(),
+            ],
+        )
+        .await
+        .map_err(GitCmdError::Clone)
+    }
 }
 
 fn wrap_cmd<I, S>(cmd: &str, args: I) -> io::Result<Output>
@@ -157,6 +400,83 @@ where
     Ok(output)
 }
 
+fn wrap_cmd_with_env<I, S>(cmd: &str, args: I, env_key: &str, env_value: &str) -> io::Result<Output>
+where
+    I: IntoIterator<Item = S>,
+    S: AsRef<OsStr>,
+{
+    let output = pipe_io(Command::new(cmd).args(args).env(env_key, env_value))
+        .spawn()?
+        .wait_with_output()?;
+
+    log_output(&output);
+
+    Ok(output)
+}
+
+/// Writes a throwaway `SSH_ASKPASS` helper that echoes `passphrase` back to ssh, so a
+/// passphrase-protected key can be used non-interactively in [`Git::clone_auth`]. Callers are
+/// responsible for removing the file once the clone finishes.
+///
+/// The path is keyed by both the process id and a per-call counter (not just the pid), and the
+/// file is opened with `create_new` + `mode(0o700)` in one syscall, so two concurrent
+/// `clone_auth` calls in this process never share a path (no clobbered/deleted-out-from-under-it
+/// script), the passphrase is never briefly world-readable between creation and chmod, and the
+/// script stays executable so `ssh` can actually invoke it as `SSH_ASKPASS`.
+fn write_askpass_script(passphrase: &SecretString) -> io::Result<PathBuf> {
+    use std::fs::OpenOptions;
+    use std::io::Write;
+    use std::os::unix::fs::OpenOptionsExt;
+    use std::sync::atomic::{AtomicU64, Ordering};
+
+    static CALL_COUNTER: AtomicU64 = AtomicU64::new(0);
+
+    let escaped = passphrase.expose_secret().replace('\'', "'\\''");
+    let unique = CALL_COUNTER.fetch_add(1, Ordering::Relaxed);
+    let path = std::env::temp_dir().join(format!(
+        "git-repo-askpass-{}-{unique}.sh",
+        std::process::id()
+    ));
+
+    let mut file = OpenOptions::new()
+        .write(true)
+        .create_new(true)
+        .mode(0o700)
+        .open(&path)?;
+    writeln!(file, "#!/bin/sh")?;
+    writeln!(file, "echo '{escaped}'")?;
+
+    Ok(path)
+}
+
+/// Same as [`wrap_cmd_with_env`], but additionally pointing `SSH_ASKPASS` at `askpass_path` so
+/// ssh can retrieve a passphrase non-interactively instead of hanging on a tty prompt.
+fn wrap_cmd_with_ssh_askpass<I, S>(
+    cmd: &str,
+    args: I,
+    ssh_command: &str,
+    askpass_path: &Path,
+) -> io::Result<Output>
+where
+    I: IntoIterator<Item = S>,
+    S: AsRef<OsStr>,
+{
+    let output = pipe_io(
+        Command::new(cmd)
+            .args(args)
+            .env("GIT_SSH_COMMAND", ssh_command)
+            .env("SSH_ASKPASS", askpass_path)
+            .env("SSH_ASKPASS_REQUIRE", "force")
+            .env("DISPLAY", ":0"),
+    )
+    .spawn()?
+    .wait_with_output()?;
+
+    log_output(&output);
+
+    Ok(output)
+}
+
 fn wrap_cmd_dir<I, S, P>(cmd: &str, args: I, path: P) -> io::Result<Output>
 where
     I: IntoIterator<Item = S>,
@@ -176,6 +496,25 @@ pub fn pipe_io(cmd: &mut Command) -> &mut Command {
     cmd.stdout(Stdio::piped()).stderr(Stdio::piped())
 }
 
+async fn wrap_cmd_async<I, S>(cmd: &str, args: I) -> io::Result<Output>
+where
+    I: IntoIterator<Item = S>,
+    S: AsRef<OsStr>,
+{
+    let output = pipe_io_async(AsyncCommand::new(cmd).args(args))
+        .spawn()?
+        .wait_with_output()
+        .await?;
+
+    log_output(&output);
+
+    Ok(output)
+}
+
+pub fn pipe_io_async(cmd: &mut AsyncCommand) -> &mut AsyncCommand {
+    cmd.stdout(Stdio::piped()).stderr(Stdio::piped())
+}
+
 pub fn log_output(output: &Output) {
     // Use log crate to allow verbosity flag to control wrapped command logs.
     if output.status.success() && !output.stdout.is_empty() {
@@ -194,8 +533,9 @@ mod tests {
     use assert_fs::*;
     use predicates::prelude::*;
     use rstest::{fixture, rstest};
+    use secrecy::SecretString;
 
-    use super::Git;
+    use super::{write_askpass_script, CloneOptions, Credentials, Git, GitCmdError};
 
     #[fixture]
     fn temp_directory_fs() -> TempDir {
@@ -210,6 +550,34 @@ mod tests {
         temp_directory_fs
     }
 
+    #[fixture]
+    fn temp_repo_with_commit_fs(temp_repo_fs: TempDir) -> TempDir {
+        // Arrange
+        let path = temp_repo_fs.path();
+        std::process::Command::new("git")
+            .args(["config", "user.email", "test@example.com"])
+            .current_dir(path)
+            .status()
+            .expect("should be able to set git config");
+        std::process::Command::new("git")
+            .args(["config", "user.name", "Test"])
+            .current_dir(path)
+            .status()
+            .expect("should be able to set git config");
+        fs::write(path.join("README.md"), "hello").expect("should be able to write file");
+        std::process::Command::new("git")
+            .args(["add", "."])
+            .current_dir(path)
+            .status()
+            .expect("should be able to stage files");
+        std::process::Command::new("git")
+            .args(["commit", "-m", "initial commit"])
+            .current_dir(path)
+            .status()
+            .expect("should be able to commit");
+        temp_repo_fs
+    }
+
     #[rstest]
     fn should_init_directory_as_git_repo(temp_directory_fs: TempDir) -> Result<()> {
         // Arrange / Act
@@ -250,4 +618,188 @@ mod tests {
 
         Ok(())
     }
+
+    #[rstest]
+    fn log_structured_parses_one_commit_per_record(temp_repo_with_commit_fs: TempDir) -> Result<()> {
+        // Act
+        let commits = Git::log_structured(&temp_repo_with_commit_fs.path(), None)?;
+
+        // Assert
+        assert_eq!(commits.len(), 1);
+        assert_eq!(commits[0].subject, "initial commit");
+        assert_eq!(commits[0].author_name, "Test");
+        assert_eq!(commits[0].author_email, "test@example.com");
+        assert_eq!(commits[0].sha.0.len(), 40);
+
+        Ok(())
+    }
+
+    #[rstest]
+    fn log_structured_respects_the_limit(temp_repo_with_commit_fs: TempDir) -> Result<()> {
+        // Arrange
+        fs::write(temp_repo_with_commit_fs.path().join("CHANGES.md"), "more")?;
+        std::process::Command::new("git")
+            .args(["add", "."])
+            .current_dir(temp_repo_with_commit_fs.path())
+            .status()
+            .expect("should be able to stage files");
+        std::process::Command::new("git")
+            .args(["commit", "-m", "second commit"])
+            .current_dir(temp_repo_with_commit_fs.path())
+            .status()
+            .expect("should be able to commit");
+
+        // Act
+        let commits = Git::log_structured(&temp_repo_with_commit_fs.path(), Some(1))?;
+
+        // Assert
+        assert_eq!(commits.len(), 1);
+        assert_eq!(commits[0].subject, "second commit");
+
+        Ok(())
+    }
+
+    #[test]
+    fn write_askpass_script_is_private_and_executable() {
+        use std::os::unix::fs::PermissionsExt;
+
+        // Arrange
+        let passphrase: SecretString = "hunter2".to_string().into();
+
+        // Act
+        let path = write_askpass_script(&passphrase).expect("should be able to write script");
+
+        // Assert
+        let mode = fs::metadata(&path)
+            .expect("script should exist")
+            .permissions()
+            .mode();
+        assert_eq!(
+            mode & 0o777,
+            0o700,
+            "SSH_ASKPASS script must stay private (no group/other access) but owner-executable"
+        );
+
+        let _ = fs::remove_file(&path);
+    }
+
+    #[test]
+    fn write_askpass_script_uses_a_distinct_path_per_call() {
+        // Arrange
+        let passphrase: SecretString = "hunter2".to_string().into();
+
+        // Act
+        let first = write_askpass_script(&passphrase).expect("should be able to write script");
+        let second = write_askpass_script(&passphrase).expect("should be able to write script");
+
+        // Assert
+        assert_ne!(first, second, "concurrent clone_auth calls must not share an askpass path");
+
+        let _ = fs::remove_file(&first);
+        let _ = fs::remove_file(&second);
+    }
+
+    #[rstest]
+    fn clone_with_threads_branch_and_depth_into_a_shallow_single_branch_checkout(
+        temp_repo_with_commit_fs: TempDir,
+        temp_directory_fs: TempDir,
+    ) {
+        // Arrange
+        let dest = temp_directory_fs.path().join("dest");
+        let options = CloneOptions {
+            branch: Some("master".to_string()),
+            depth: std::num::NonZeroU32::new(1),
+            single_branch: true,
+            recurse_submodules: false,
+        };
+
+        // A `file://` url rather than a bare path: git ignores `--depth` for plain-path local clones.
+        let origin_url = format!("file://{}", temp_repo_with_commit_fs.path().display());
+
+        // Act
+        Git::clone_with(&origin_url, &dest, &options).expect("should not fail");
+
+        // Assert
+        assert!(Git::is_inside_worktree(&dest));
+        assert!(
+            predicate::path::exists().eval(&dest.join(".git").join("shallow")),
+            "--depth 1 should have produced a shallow clone"
+        );
+    }
+
+    #[rstest]
+    fn clone_auth_with_ssh_credentials_clones_successfully(
+        temp_repo_with_commit_fs: TempDir,
+        temp_directory_fs: TempDir,
+    ) -> Result<()> {
+        // Arrange: the remote is a local path, so ssh is never actually invoked - this exercises
+        // `clone_auth`'s `GIT_SSH_COMMAND` plumbing without needing a real ssh server.
+        let dest = temp_directory_fs.path().join("dest");
+        let credentials = Credentials::Ssh {
+            key_path: temp_directory_fs.path().join("id_ed25519"),
+            passphrase: None,
+        };
+
+        // Act
+        Git::clone_auth(&temp_repo_with_commit_fs.path().to_string_lossy(), &dest, &credentials)?;
+
+        // Assert
+        assert!(Git::is_inside_worktree(&dest));
+
+        Ok(())
+    }
+
+    #[rstest]
+    fn clone_auth_with_an_ssh_passphrase_cleans_up_its_askpass_script(
+        temp_repo_with_commit_fs: TempDir,
+        temp_directory_fs: TempDir,
+    ) -> Result<()> {
+        // Arrange
+        let dest = temp_directory_fs.path().join("dest");
+        let credentials = Credentials::Ssh {
+            key_path: temp_directory_fs.path().join("id_ed25519"),
+            passphrase: Some(SecretString::from("hunter2".to_string())),
+        };
+        let before: Vec<_> = fs::read_dir(std::env::temp_dir())?
+            .filter_map(|e| e.ok())
+            .filter(|e| e.file_name().to_string_lossy().starts_with("git-repo-askpass-"))
+            .collect();
+
+        // Act
+        Git::clone_auth(&temp_repo_with_commit_fs.path().to_string_lossy(), &dest, &credentials)?;
+
+        // Assert
+        assert!(Git::is_inside_worktree(&dest));
+        let after: Vec<_> = fs::read_dir(std::env::temp_dir())?
+            .filter_map(|e| e.ok())
+            .filter(|e| e.file_name().to_string_lossy().starts_with("git-repo-askpass-"))
+            .collect();
+        assert_eq!(
+            before.len(),
+            after.len(),
+            "the askpass helper script should be removed once the clone finishes"
+        );
+
+        Ok(())
+    }
+
+    #[rstest]
+    fn clone_auth_with_https_credentials_rejects_a_non_http_remote(temp_directory_fs: TempDir) {
+        // Arrange: an ssh-shorthand remote parses fine, but has no http(s) scheme to embed basic
+        // auth credentials into.
+        let credentials = Credentials::Https {
+            user: "octocat".to_string(),
+            token: SecretString::from("token123".to_string()),
+        };
+
+        // Act
+        let result = Git::clone_auth(
+            "git@github.com:pitoniak32/git_repo.git",
+            &temp_directory_fs.path().join("dest"),
+            &credentials,
+        );
+
+        // Assert
+        assert!(matches!(result, Err(GitCmdError::InvalidCredentialUrl(_))));
+    }
 }
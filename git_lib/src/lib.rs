@@ -0,0 +1,6 @@
+pub mod aliases;
+pub mod backend;
+pub mod git;
+pub mod git_uri;
+pub mod manifest;
+pub mod repo;
@@ -0,0 +1,284 @@
+use std::{
+    io,
+    path::{Path, PathBuf},
+};
+
+use serde::Deserialize;
+use thiserror::Error;
+
+use crate::git::Git;
+use crate::repo::{GitRepo, GitRepoError};
+
+#[derive(Error, Debug)]
+pub enum ManifestError {
+    #[error("failed to read manifest at {0}: {1}")]
+    Read(String, #[source] io::Error),
+
+    #[error("failed to parse manifest: {0}")]
+    Parse(#[from] toml::de::Error),
+}
+
+/// A declarative description of the repos a workspace wants present, reconciled by
+/// [`GitRepo::sync_manifest`].
+#[derive(Debug, Deserialize)]
+pub struct Manifest {
+    #[serde(rename = "repo")]
+    pub repos: Vec<RepoEntry>,
+}
+
+#[derive(Debug, Deserialize)]
+pub struct RepoEntry {
+    pub name: String,
+    pub url: String,
+    pub path: Option<PathBuf>,
+
+    #[serde(default = "default_true")]
+    pub clone: bool,
+
+    #[serde(default = "default_true")]
+    pub pull: bool,
+
+    #[serde(default)]
+    pub skip: bool,
+}
+
+fn default_true() -> bool {
+    true
+}
+
+impl Manifest {
+    pub fn from_path(path: &Path) -> Result<Manifest, ManifestError> {
+        let content = std::fs::read_to_string(path)
+            .map_err(|e| ManifestError::Read(path.to_string_lossy().to_string(), e))?;
+        Ok(toml::from_str(&content)?)
+    }
+}
+
+impl GitRepo {
+    /// Reconciles every repo described by the manifest at `path`: cloning the ones that are
+    /// missing and flagged `clone`, fetching/pulling the ones flagged `pull`, and logging a
+    /// per-repo status summary. Repos flagged `skip` are left untouched.
+    pub fn sync_manifest(
+        path: &Path,
+    ) -> Result<Vec<(String, Result<GitRepo, GitRepoError>)>, ManifestError> {
+        let manifest = Manifest::from_path(path)?;
+        let mut results = Vec::with_capacity(manifest.repos.len());
+
+        for entry in manifest.repos {
+            if entry.skip {
+                log::info!("{}: skipped", entry.name);
+                continue;
+            }
+
+            let dest = entry.path.clone().unwrap_or_else(|| default_dest(&entry.url, &entry.name));
+
+            let result = if !dest.exists() {
+                if !entry.clone {
+                    log::info!("{}: missing and not flagged `clone`, leaving as-is", entry.name);
+                    continue;
+                }
+                GitRepo::from_url(&entry.url, &dest)
+            } else if entry.pull {
+                GitRepo::ensure(&entry.url, &dest)
+            } else {
+                GitRepo::from_existing(&dest)
+            };
+
+            match &result {
+                Ok(repo) => log::info!("{}: synced at {}", entry.name, repo.root_path.display()),
+                Err(e) => log::warn!("{}: failed to sync: {e}", entry.name),
+            }
+
+            results.push((entry.name, result));
+        }
+
+        Ok(results)
+    }
+}
+
+fn default_dest(url: &str, fallback_name: &str) -> PathBuf {
+    match Git::parse_uri(url) {
+        Ok(uri) => PathBuf::from(uri.owner.unwrap_or_default()).join(uri.name),
+        Err(_) => PathBuf::from(fallback_name),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    use assert_fs::TempDir;
+    use rstest::{fixture, rstest};
+
+    #[fixture]
+    fn temp_directory_fs() -> TempDir {
+        // Arrange
+        TempDir::new().expect("should be able to make temp dir")
+    }
+
+    /// Inits a git repo at `path` with one commit, so it can stand in as a local filesystem
+    /// clone source for `sync_manifest`, without reaching out to a real remote.
+    fn init_origin_with_commit(path: &Path) {
+        std::fs::create_dir_all(path).expect("should be able to make origin dir");
+        Git::init(path).expect("git repo should init in temp dir");
+        std::process::Command::new("git")
+            .args(["config", "user.email", "test@example.com"])
+            .current_dir(path)
+            .status()
+            .expect("should be able to set git config");
+        std::process::Command::new("git")
+            .args(["config", "user.name", "Test"])
+            .current_dir(path)
+            .status()
+            .expect("should be able to set git config");
+        std::fs::write(path.join("README.md"), "hello").expect("should be able to write file");
+        std::process::Command::new("git")
+            .args(["add", "."])
+            .current_dir(path)
+            .status()
+            .expect("should be able to stage files");
+        std::process::Command::new("git")
+            .args(["commit", "-m", "initial commit"])
+            .current_dir(path)
+            .status()
+            .expect("should be able to commit");
+    }
+
+    fn commit_new_file(path: &Path) {
+        std::fs::write(path.join("CHANGES.md"), "more").expect("should be able to write file");
+        std::process::Command::new("git")
+            .args(["add", "."])
+            .current_dir(path)
+            .status()
+            .expect("should be able to stage files");
+        std::process::Command::new("git")
+            .args(["commit", "-m", "second commit"])
+            .current_dir(path)
+            .status()
+            .expect("should be able to commit");
+    }
+
+    #[rstest]
+    fn sync_manifest_clones_pulls_and_skips_per_entry_flags(temp_directory_fs: TempDir) {
+        // Arrange: `cloned` doesn't exist locally yet and should be cloned; `pulled` is already
+        // cloned and gets a new upstream commit it should be fast-forwarded onto; `skipped` is
+        // missing and flagged `skip`, so it must be left untouched.
+        let cloned_origin = temp_directory_fs.path().join("origins").join("cloned.git");
+        let pulled_origin = temp_directory_fs.path().join("origins").join("pulled.git");
+        init_origin_with_commit(&cloned_origin);
+        init_origin_with_commit(&pulled_origin);
+
+        let cloned_dest = temp_directory_fs.path().join("repos").join("cloned");
+        let pulled_dest = temp_directory_fs.path().join("repos").join("pulled");
+        let skipped_dest = temp_directory_fs.path().join("repos").join("skipped");
+
+        GitRepo::from_url(&pulled_origin.to_string_lossy(), &pulled_dest)
+            .expect("should be able to pre-clone the `pulled` entry");
+        commit_new_file(&pulled_origin);
+
+        let manifest_path = temp_directory_fs.path().join("manifest.toml");
+        std::fs::write(
+            &manifest_path,
+            format!(
+                r#"
+                [[repo]]
+                name = "cloned"
+                url = "{cloned_origin}"
+                path = "{cloned_dest}"
+
+                [[repo]]
+                name = "pulled"
+                url = "{pulled_origin}"
+                path = "{pulled_dest}"
+
+                [[repo]]
+                name = "skipped"
+                url = "{pulled_origin}"
+                path = "{skipped_dest}"
+                skip = true
+                "#,
+                cloned_origin = cloned_origin.to_string_lossy(),
+                cloned_dest = cloned_dest.to_string_lossy(),
+                pulled_origin = pulled_origin.to_string_lossy(),
+                pulled_dest = pulled_dest.to_string_lossy(),
+                skipped_dest = skipped_dest.to_string_lossy(),
+            ),
+        )
+        .expect("should be able to write manifest");
+
+        // Act
+        let results = GitRepo::sync_manifest(&manifest_path).expect("should not fail");
+
+        // Assert
+        assert_eq!(results.len(), 2, "skipped entry should not appear in results");
+
+        let results: std::collections::HashMap<_, _> = results.into_iter().collect();
+
+        let cloned = results
+            .get("cloned")
+            .expect("cloned entry should be present")
+            .as_ref()
+            .expect("cloned entry should have synced");
+        assert!(cloned_dest.exists());
+        assert_eq!(cloned.remote_url, Some(cloned_origin.to_string_lossy().to_string()));
+
+        let pulled = results
+            .get("pulled")
+            .expect("pulled entry should be present")
+            .as_ref()
+            .expect("pulled entry should have synced");
+        assert_eq!(
+            Git::current_commit(&pulled_dest).expect("should be able to read HEAD"),
+            Git::current_commit(&pulled_origin).expect("should be able to read HEAD"),
+            "pulled entry should be fast-forwarded onto the origin's new commit"
+        );
+        assert!(!skipped_dest.exists(), "skipped entry must be left untouched");
+    }
+
+    #[test]
+    fn from_path_parses_repo_entries_with_their_defaults() {
+        let dir = assert_fs::TempDir::new().expect("should be able to make temp dir");
+        let manifest_path = dir.join("manifest.toml");
+        std::fs::write(
+            &manifest_path,
+            r#"
+            [[repo]]
+            name = "git_repo"
+            url = "https://github.com/pitoniak32/git_repo.git"
+
+            [[repo]]
+            name = "actions"
+            url = "https://github.com/pitoniak32/actions.git"
+            path = "/tmp/actions"
+            pull = false
+            skip = true
+            "#,
+        )
+        .expect("should be able to write manifest");
+
+        let manifest = Manifest::from_path(&manifest_path).expect("should parse");
+
+        assert_eq!(manifest.repos.len(), 2);
+        assert!(manifest.repos[0].clone);
+        assert!(manifest.repos[0].pull);
+        assert!(!manifest.repos[0].skip);
+        assert_eq!(manifest.repos[0].path, None);
+
+        assert!(!manifest.repos[1].pull);
+        assert!(manifest.repos[1].skip);
+        assert_eq!(manifest.repos[1].path, Some(PathBuf::from("/tmp/actions")));
+    }
+
+    #[test]
+    fn default_dest_falls_back_to_the_provided_name_for_an_unparseable_url() {
+        assert_eq!(default_dest("not a url", "fallback"), PathBuf::from("fallback"));
+    }
+
+    #[test]
+    fn default_dest_uses_the_parsed_owner_and_repo_name() {
+        assert_eq!(
+            default_dest("https://github.com/pitoniak32/git_repo.git", "fallback"),
+            PathBuf::from("pitoniak32").join("git_repo")
+        );
+    }
+}